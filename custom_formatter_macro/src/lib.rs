@@ -1,5 +1,6 @@
+use std::collections::{HashMap, HashSet};
+
 use proc_macro::TokenStream;
-use quote::ToTokens;
 use syn::{
     parse::Parse, parse_macro_input, punctuated::Punctuated, Expr, Ident, LitStr, Path, Token,
 };
@@ -9,81 +10,303 @@ pub fn custom_format_args(stream: TokenStream) -> TokenStream {
     let macro_args = parse_macro_input!(stream as FormatArgs);
     let custom_formatter = macro_args.custom_format_crate;
 
-    let args: Vec<Expr> = macro_args
-        .args
-        .pairs()
-        .filter_map(|pair| pair.punct().copied().cloned())
-        .collect();
+    // Split the supplied arguments into positional ones (indexable by `{}`/`{N}`) and explicit
+    // named ones (indexable by `{name}`), preserving the order each group was supplied in.
+    let mut positional_exprs: Vec<Expr> = Vec::new();
+    let mut named_exprs: HashMap<String, Expr> = HashMap::new();
+    let mut named_idents: Vec<Ident> = Vec::new();
+    for arg in macro_args.args {
+        match arg {
+            MacroArg::Positional(expr) => positional_exprs.push(expr),
+            MacroArg::Named(name, expr) => {
+                if named_exprs.contains_key(&name.to_string()) {
+                    return syn::Error::new_spanned(
+                        &name,
+                        format!("duplicate named argument `{name}`"),
+                    )
+                    .into_compile_error()
+                    .into();
+                }
+                named_exprs.insert(name.to_string(), expr);
+                named_idents.push(name);
+            }
+        }
+    }
 
     let pieces: Vec<LitStr> = macro_args.format_str.args.iter().cloned().collect();
 
-    let mut args_iter = args.iter();
-    //TODO: this will actually reevalute arguments multiple times if they are specified multiple
-    // times, unlike format!("{0}{0}", func()), which evaluates func() only once.
-    let args_reordered: Vec<Expr> = match macro_args
+    let mut used_named: HashSet<String> = HashSet::new();
+    let mut value_exprs: Vec<Expr> = Vec::new();
+    let mut spec_exprs: Vec<proc_macro2::TokenStream> = Vec::new();
+    let mut positional_iter = 0usize;
+
+    for slot in macro_args
         .format_str
         .args
         .pairs()
         .filter_map(|p| p.punct().copied().cloned())
-        .map(|arg| match arg {
-            FormatArgument::Positional => args_iter.next().cloned().ok_or_else(|| {
-                syn::Error::new_spanned(
-                    &macro_args.format_str.lit,
-                    "format string missing positional argument",
-                )
-            }),
-            FormatArgument::Numbered(n) => args.get(n).cloned().ok_or_else(|| {
-                syn::Error::new_spanned(
-                    &macro_args.format_str.lit,
-                    "numbered argument out of range",
-                )
-            }),
-            FormatArgument::Named(name) => Ok(syn::Expr::Verbatim(name.into_token_stream())),
-        })
-        .collect::<Result<Vec<Expr>, syn::Error>>()
     {
-        Ok(a) => a,
-        Err(e) => return e.into_compile_error().into(),
-    };
+        let value_expr = match &slot.arg {
+            ArgRef::Positional => {
+                let expr = match positional_exprs.get(positional_iter) {
+                    Some(expr) => expr.clone(),
+                    None => {
+                        return syn::Error::new_spanned(
+                            &macro_args.format_str.lit,
+                            "format string missing positional argument",
+                        )
+                        .into_compile_error()
+                        .into()
+                    }
+                };
+                positional_iter += 1;
+                expr
+            }
+            ArgRef::Numbered(n) => match positional_exprs.get(*n) {
+                Some(expr) => expr.clone(),
+                None => {
+                    return syn::Error::new_spanned(
+                        &macro_args.format_str.lit,
+                        "numbered argument out of range",
+                    )
+                    .into_compile_error()
+                    .into()
+                }
+            },
+            ArgRef::Named(name) => resolve_named(name, &named_exprs, &mut used_named),
+        };
 
+        let spec_expr = match spec_to_tokens(
+            &slot.spec,
+            &custom_formatter,
+            &positional_exprs,
+            &named_exprs,
+            &mut used_named,
+        ) {
+            Ok(tokens) => tokens,
+            Err(e) => return e.into_compile_error().into(),
+        };
+
+        value_exprs.push(value_expr);
+        spec_exprs.push(spec_expr);
+    }
+
+    for name in &named_idents {
+        if !used_named.contains(&name.to_string()) {
+            return syn::Error::new_spanned(name, format!("named argument `{name}` not used"))
+                .into_compile_error()
+                .into();
+        }
+    }
+
+    // NOTE: each reference to the same positional/numbered/named argument reevaluates its
+    // expression (so `{0}{0}` behaves like `x(); x()`, not like `format!`'s evaluate-once
+    // semantics), because `Arguments`/`Argument` store borrowed slices behind a public
+    // constructor rather than owning their data: evaluating every argument exactly once and
+    // feeding the results into a freshly-built borrowed `&[Argument]` would require returning a
+    // reference to a local out of the scope that creates it, which isn't expressible in safe
+    // Rust without `Arguments`/`Argument` owning their contents instead of borrowing them.
     quote::quote! {
-        #custom_formatter::Arguments::new(&[#(#pieces),*], &[#(#custom_formatter::Argument::from_ref(&#args_reordered)),*])
+        #custom_formatter::Arguments::new(&[#(#pieces),*], &[#(#custom_formatter::Argument::from_ref(&(#value_exprs), #spec_exprs)),*])
     }
     .into()
 }
 
+/// Resolve a `{name}`/`name$` reference: prefer an explicit `name = expr` binding, falling back
+/// to capturing an identifier of that name from the surrounding scope, as today.
+fn resolve_named(
+    name: &Ident,
+    named_exprs: &HashMap<String, Expr>,
+    used_named: &mut HashSet<String>,
+) -> Expr {
+    let key = name.to_string();
+    match named_exprs.get(&key) {
+        Some(expr) => {
+            used_named.insert(key);
+            expr.clone()
+        }
+        None => Expr::Verbatim(quote::quote! { #name }),
+    }
+}
+
+/// Turn a parsed [`FormatSpec`] into the tokens for a runtime `custom_formatter::FormatSpec`
+/// value, resolving any `N$`/`name$` width or precision references along the way.
+fn spec_to_tokens(
+    spec: &FormatSpec,
+    custom_formatter: &Path,
+    positional_exprs: &[Expr],
+    named_exprs: &HashMap<String, Expr>,
+    used_named: &mut HashSet<String>,
+) -> syn::Result<proc_macro2::TokenStream> {
+    let fill = spec.fill;
+    let align = match spec.align {
+        Some(Align::Left) => quote::quote! { ::core::option::Option::Some(#custom_formatter::Align::Left) },
+        Some(Align::Center) => quote::quote! { ::core::option::Option::Some(#custom_formatter::Align::Center) },
+        Some(Align::Right) => quote::quote! { ::core::option::Option::Some(#custom_formatter::Align::Right) },
+        None => quote::quote! { ::core::option::Option::None },
+    };
+    let sign_plus = spec.sign_plus;
+    let alternate = spec.alternate;
+    let zero = spec.zero;
+    let width = count_to_tokens(&spec.width, positional_exprs, named_exprs, used_named)?;
+    let precision = count_to_tokens(&spec.precision, positional_exprs, named_exprs, used_named)?;
+    let ty = &spec.ty;
+
+    Ok(quote::quote! {
+        #custom_formatter::FormatSpec {
+            fill: #fill,
+            align: #align,
+            sign_plus: #sign_plus,
+            alternate: #alternate,
+            zero: #zero,
+            width: #width,
+            precision: #precision,
+            ty: #ty,
+        }
+    })
+}
+
+fn count_to_tokens(
+    count: &Option<Count>,
+    positional_exprs: &[Expr],
+    named_exprs: &HashMap<String, Expr>,
+    used_named: &mut HashSet<String>,
+) -> syn::Result<proc_macro2::TokenStream> {
+    Ok(match count {
+        None => quote::quote! { ::core::option::Option::None },
+        Some(Count::Literal(n)) => quote::quote! { ::core::option::Option::Some(#n) },
+        Some(Count::Positional(n)) => {
+            let expr = positional_exprs.get(*n).ok_or_else(|| {
+                syn::Error::new(
+                    proc_macro2::Span::call_site(),
+                    "numbered argument out of range",
+                )
+            })?;
+            quote::quote! { ::core::option::Option::Some((#expr) as usize) }
+        }
+        Some(Count::Named(name)) => {
+            let expr = resolve_named(name, named_exprs, used_named);
+            quote::quote! { ::core::option::Option::Some((#expr) as usize) }
+        }
+    })
+}
+
 struct FormatArgs {
     _in: Token![in],
     custom_format_crate: Path,
     _comma: Token![,],
     format_str: FormatString,
-    args: Punctuated<Token![,], Expr>,
+    args: Punctuated<MacroArg, Token![,]>,
 }
 
 impl Parse for FormatArgs {
     fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
+        let _in = input.parse()?;
+        let custom_format_crate = input.parse()?;
+        let _comma = input.parse()?;
+        let format_str = input.parse()?;
+
+        let args = if input.is_empty() {
+            Punctuated::new()
+        } else {
+            input.parse::<Token![,]>()?;
+            Punctuated::parse_terminated(input)?
+        };
+
         Ok(Self {
-            _in: input.parse()?,
-            custom_format_crate: input.parse()?,
-            _comma: input.parse()?,
-            format_str: input.parse()?,
-            args: input.parse_terminated(<Token![,]>::parse)?,
+            _in,
+            custom_format_crate,
+            _comma,
+            format_str,
+            args,
         })
     }
 }
 
+/// A trailing argument to the macro: either a bare expression, or an explicit `name = expr`
+/// binding (in the style of `format!`).
+enum MacroArg {
+    Positional(Expr),
+    Named(Ident, Expr),
+}
+
+impl Parse for MacroArg {
+    fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
+        // `peek3` excludes `==`, whose first `=` also satisfies `peek2(Token![=])`.
+        if input.peek(Ident) && input.peek2(Token![=]) && !input.peek3(Token![=]) {
+            let name: Ident = input.parse()?;
+            input.parse::<Token![=]>()?;
+            let expr: Expr = input.parse()?;
+            Ok(MacroArg::Named(name, expr))
+        } else {
+            Ok(MacroArg::Positional(input.parse()?))
+        }
+    }
+}
+
 struct FormatString {
     lit: LitStr,
-    args: Punctuated<LitStr, FormatArgument>,
+    args: Punctuated<LitStr, FormatSlot>,
 }
 
+/// What a single `{...}` refers to, alongside its parsed format specifier.
 #[derive(Clone)]
-enum FormatArgument {
+struct FormatSlot {
+    arg: ArgRef,
+    spec: FormatSpec,
+}
+
+#[derive(Clone)]
+enum ArgRef {
     Positional,
     Numbered(usize),
     Named(Ident),
 }
 
+/// A `{:[[fill]align][sign]['#']['0'][width]['.'precision][type]}` format specifier, parsed at
+/// macro-expansion time. `width`/`precision` may still refer to other arguments (`N$`/`name$`),
+/// which get resolved into the runtime `custom_formatter::FormatSpec` in [`spec_to_tokens`].
+#[derive(Clone)]
+struct FormatSpec {
+    fill: char,
+    align: Option<Align>,
+    sign_plus: bool,
+    alternate: bool,
+    zero: bool,
+    width: Option<Count>,
+    precision: Option<Count>,
+    ty: String,
+}
+
+impl Default for FormatSpec {
+    fn default() -> Self {
+        Self {
+            fill: ' ',
+            align: None,
+            sign_plus: false,
+            alternate: false,
+            zero: false,
+            width: None,
+            precision: None,
+            ty: String::new(),
+        }
+    }
+}
+
+#[derive(Clone, Copy)]
+enum Align {
+    Left,
+    Center,
+    Right,
+}
+
+#[derive(Clone)]
+enum Count {
+    Literal(usize),
+    Positional(usize),
+    Named(Ident),
+}
+
 impl Parse for FormatString {
     fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
         let lit: LitStr = input.parse()?;
@@ -114,22 +337,8 @@ impl Parse for FormatString {
                             }
                         }
                     }
-                    let argument = argument_string.as_str().trim_ascii();
-                    if argument.is_empty() {
-                        args.push_punct(FormatArgument::Positional);
-                    } else {
-                        match argument.parse() {
-                            Ok(num) => {
-                                args.push_punct(FormatArgument::Numbered(num));
-                            }
-                            Err(_) => match syn::parse_str(argument) {
-                                Ok(ident) => {
-                                    args.push_punct(FormatArgument::Named(ident));
-                                }
-                                Err(e) => return Err(syn::Error::new_spanned(lit, e)),
-                            },
-                        }
-                    }
+                    let slot = parse_slot(&argument_string, &lit)?;
+                    args.push_punct(slot);
                 }
                 ('}', Some('}')) => partial.push('}'),
                 ('}', _) => {
@@ -149,3 +358,152 @@ impl Parse for FormatString {
         Ok(Self { args, lit })
     }
 }
+
+/// Parse the contents of a single `{...}`: an optional argument reference, followed by an
+/// optional `:` and a `std::fmt`-style format specifier.
+fn parse_slot(content: &str, lit: &LitStr) -> syn::Result<FormatSlot> {
+    let mut split = content.splitn(2, ':');
+    let arg_str = split.next().unwrap_or("").trim();
+    let spec_str = split.next();
+
+    let arg = if arg_str.is_empty() {
+        ArgRef::Positional
+    } else {
+        match arg_str.parse::<usize>() {
+            Ok(num) => ArgRef::Numbered(num),
+            Err(_) => match syn::parse_str(arg_str) {
+                Ok(ident) => ArgRef::Named(ident),
+                Err(e) => return Err(syn::Error::new_spanned(lit, e)),
+            },
+        }
+    };
+
+    let spec = match spec_str {
+        Some(s) => parse_format_spec(s, lit)?,
+        None => FormatSpec::default(),
+    };
+
+    Ok(FormatSlot { arg, spec })
+}
+
+fn parse_format_spec(s: &str, lit: &LitStr) -> syn::Result<FormatSpec> {
+    let chars: Vec<char> = s.chars().collect();
+    let mut idx = 0;
+    let mut spec = FormatSpec::default();
+
+    if chars.len() >= 2 && is_align_char(chars[1]) {
+        spec.fill = chars[0];
+        spec.align = Some(align_of(chars[1]));
+        idx = 2;
+    } else if chars.first().is_some_and(|c| is_align_char(*c)) {
+        spec.align = Some(align_of(chars[0]));
+        idx = 1;
+    }
+
+    if chars.get(idx) == Some(&'+') {
+        spec.sign_plus = true;
+        idx += 1;
+    }
+
+    if chars.get(idx) == Some(&'#') {
+        spec.alternate = true;
+        idx += 1;
+    }
+
+    if chars.get(idx) == Some(&'0') && chars.get(idx + 1) != Some(&'$') {
+        spec.zero = true;
+        idx += 1;
+    }
+
+    if let Some((count, next)) = parse_count(&chars, idx) {
+        spec.width = Some(count);
+        idx = next;
+    }
+
+    if chars.get(idx) == Some(&'.') {
+        match parse_count(&chars, idx + 1) {
+            Some((count, next)) => {
+                spec.precision = Some(count);
+                idx = next;
+            }
+            None => {
+                return Err(syn::Error::new_spanned(
+                    lit,
+                    "invalid format string: expected precision after `.`",
+                ))
+            }
+        }
+    }
+
+    let ty: String = chars[idx..].iter().collect();
+    if !ty.is_empty() && ty != "?" && !is_ident_like(&ty) {
+        return Err(syn::Error::new_spanned(
+            lit,
+            format!("invalid format string: unknown format trait `{ty}`"),
+        ));
+    }
+    spec.ty = ty;
+
+    Ok(spec)
+}
+
+fn is_align_char(c: char) -> bool {
+    matches!(c, '<' | '^' | '>')
+}
+
+fn align_of(c: char) -> Align {
+    match c {
+        '<' => Align::Left,
+        '^' => Align::Center,
+        '>' => Align::Right,
+        _ => unreachable!(),
+    }
+}
+
+fn is_ident_like(s: &str) -> bool {
+    let mut chars = s.chars();
+    match chars.next() {
+        Some(c) if c.is_ascii_alphabetic() || c == '_' => {}
+        _ => return false,
+    }
+    chars.all(|c| c.is_ascii_alphanumeric() || c == '_')
+}
+
+/// Try to parse a `width`/`precision` count (`N$`, `name$`, or a bare integer literal) starting
+/// at `idx`. Returns the parsed count and the index just past it, or `None` if there's no count
+/// at this position.
+fn parse_count(chars: &[char], idx: usize) -> Option<(Count, usize)> {
+    let digits_end = idx
+        + chars[idx..]
+            .iter()
+            .take_while(|c| c.is_ascii_digit())
+            .count();
+    if digits_end > idx {
+        let num: usize = chars[idx..digits_end].iter().collect::<String>().parse().ok()?;
+        return if chars.get(digits_end) == Some(&'$') {
+            Some((Count::Positional(num), digits_end + 1))
+        } else {
+            Some((Count::Literal(num), digits_end))
+        };
+    }
+
+    let ident_end = idx
+        + chars[idx..]
+            .iter()
+            .enumerate()
+            .take_while(|(i, c)| {
+                if *i == 0 {
+                    c.is_ascii_alphabetic() || **c == '_'
+                } else {
+                    c.is_ascii_alphanumeric() || **c == '_'
+                }
+            })
+            .count();
+    if ident_end > idx && chars.get(ident_end) == Some(&'$') {
+        let name: String = chars[idx..ident_end].iter().collect();
+        let ident = syn::parse_str::<Ident>(&name).ok()?;
+        return Some((Count::Named(ident), ident_end + 1));
+    }
+
+    None
+}
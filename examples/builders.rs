@@ -0,0 +1,26 @@
+use custom_formatter::{
+    builders::debug_struct, custom_format, DebugFormatter, Format, FormatTarget,
+};
+
+// Not `derive(Debug)`: that would conflict with the blanket `impl<T: Debug> Format<DebugFormatter>
+// for T`, since this type provides its own `Format<DebugFormatter>` impl instead.
+struct Point {
+    x: i32,
+    y: i32,
+}
+
+impl Format<DebugFormatter> for Point {
+    fn fmt(&self, f: &mut DebugFormatter) -> Result<(), <DebugFormatter as FormatTarget>::Error> {
+        debug_struct(f, "Point")
+            .field("x", &self.x)
+            .field("y", &self.y)
+            .finish()
+    }
+}
+
+fn main() {
+    let point = Point { x: 1, y: 2 };
+    let s: String = custom_format!(with DebugFormatter, "{:?}", point);
+
+    println!("{s}"); // Prints Point { x: 1, y: 2 }
+}
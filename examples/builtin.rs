@@ -12,4 +12,13 @@ fn main() {
     let c: Vec<u8> = custom_format!("hello world number {}\n", b'3');
 
     stdout().write(&c).unwrap(); // Prints hello world number 3
+
+    // Format specs: width/align/precision, and by-index/by-name argument references.
+    let d = custom_format!(with DisplayFormatter, "[{:>8}]", "hi"); // Prints [      hi]
+    let e = custom_format!(with DisplayFormatter, "[{:.2}]", "hello"); // Prints [he]
+    let f = custom_format!(with DisplayFormatter, "{0} {0} {name}", "again", name = "named");
+
+    println!("{d}");
+    println!("{e}");
+    println!("{f}"); // Prints again again named
 }
@@ -1,6 +1,6 @@
 use std::fmt::{Display, Write};
 
-use custom_formatter::{custom_format, CustomFormatter, Format};
+use custom_formatter::{custom_format, CustomFormatter, Format, FormatTarget};
 
 struct ColoredString {
     fragments: Vec<ColoredFragment>,
@@ -60,27 +60,28 @@ trait ColorExt: Into<ColoredFragment> {
 
 impl<T> ColorExt for T where T: Into<ColoredFragment> {}
 
+impl FormatTarget for ColoredString {
+    type Error = ();
+    fn push_piece(&mut self, piece: &str) -> Result<(), Self::Error> {
+        self.push_fragment(piece.white());
+
+        Ok(())
+    }
+}
+
 impl CustomFormatter for ColoredString {
     type Output = Self;
-    type Error = ();
     fn from_args(args: custom_formatter::Arguments<'_, Self>) -> Result<Self::Output, Self::Error> {
         let mut string = ColoredString {
             fragments: Vec::new(),
         };
-
-        for (piece, arg) in args {
-            string.push_fragment(piece.white());
-            if let Some(arg) = arg {
-                arg.fmt(&mut string)?;
-            }
-        }
-
+        args.write_into(&mut string)?;
         Ok(string)
     }
 }
 
 impl Format<ColoredString> for &str {
-    fn fmt(&self, f: &mut ColoredString) -> Result<(), <ColoredString as CustomFormatter>::Error> {
+    fn fmt(&self, f: &mut ColoredString) -> Result<(), <ColoredString as FormatTarget>::Error> {
         f.push_fragment((*self).into());
 
         Ok(())
@@ -88,7 +89,7 @@ impl Format<ColoredString> for &str {
 }
 
 impl Format<ColoredString> for ColoredFragment {
-    fn fmt(&self, f: &mut ColoredString) -> Result<(), <ColoredString as CustomFormatter>::Error> {
+    fn fmt(&self, f: &mut ColoredString) -> Result<(), <ColoredString as FormatTarget>::Error> {
         f.push_fragment(self.clone());
 
         Ok(())
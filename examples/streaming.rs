@@ -0,0 +1,13 @@
+use custom_formatter::{custom_format, FmtWriterFormatter, WriterFormatter};
+
+fn main() {
+    // WriterFormatter<W: io::Write> streams each piece/argument straight into `W` as it's
+    // formatted, instead of buffering the whole result in memory like the plain `Vec<u8>` impl
+    // does. Any `Default` sink can still go through `custom_format!` directly.
+    let bytes = custom_format!(with WriterFormatter<Vec<u8>>, "hello {} world\n", "streaming");
+    print!("{}", String::from_utf8(bytes).unwrap()); // Prints hello streaming world
+
+    // FmtWriterFormatter<W: fmt::Write> is the same idea for a `fmt::Write` sink.
+    let s = custom_format!(with FmtWriterFormatter<String>, "hello {} world", "fmt streaming");
+    println!("{s}"); // Prints hello fmt streaming world
+}
@@ -0,0 +1,18 @@
+use custom_formatter::{custom_write, custom_writeln, WriterFormatter};
+
+fn main() -> std::io::Result<()> {
+    // custom_write!/custom_writeln! append into an already-open target in place, rather than
+    // building a fresh one each time - useful for a sink that isn't `Default`, like an open file.
+    let path = std::env::temp_dir().join("custom_formatter_write_example.txt");
+    let mut out = WriterFormatter::new(std::fs::File::create(&path)?);
+
+    custom_write!(out, "hello {}", "world")?;
+    custom_writeln!(out, "!")?;
+    custom_write!(out, "line two")?;
+    drop(out);
+
+    println!("{}", std::fs::read_to_string(&path)?); // Prints hello world!\nline two
+    std::fs::remove_file(&path)?;
+
+    Ok(())
+}
@@ -0,0 +1,270 @@
+//! Helpers for building structured `Debug`-style output over any [`FormatTarget`], mirroring
+//! `std::fmt`'s `DebugStruct`/`DebugTuple`/`DebugList`/`DebugSet`/`DebugMap`.
+//!
+//! Struct/field/variant names and structural separators (braces, `", "`, `": "`, ...) are written
+//! via [`FormatTarget::push_piece`], not [`Format::fmt`] - so they come out verbatim even for a
+//! formatter like [`DebugFormatter`] whose `&str: Format<Self>` impl quotes its arguments. Field
+//! and entry values still go through [`Format::fmt`], so they're formatted (and, for `&str` on
+//! `DebugFormatter`, quoted) the same way any other argument would be.
+
+use super::*;
+
+/// Build a `Name { field: value, .. }`-style debug representation. See [`DebugStruct`].
+pub fn debug_struct<'f, F: FormatTarget>(f: &'f mut F, name: &str) -> DebugStruct<'f, F> {
+    let result = f.push_piece(name);
+    DebugStruct {
+        fmt: f,
+        result,
+        has_fields: false,
+    }
+}
+
+/// A builder for a struct-style debug representation, created with [`debug_struct`].
+pub struct DebugStruct<'f, F: FormatTarget> {
+    fmt: &'f mut F,
+    result: Result<(), F::Error>,
+    has_fields: bool,
+}
+
+impl<F: FormatTarget> DebugStruct<'_, F> {
+    /// Add a field to the struct output.
+    pub fn field<T: Format<F> + ?Sized>(&mut self, name: &str, value: &T) -> &mut Self {
+        if self.result.is_ok() {
+            self.result = self.write_field(name, value);
+        }
+        self.has_fields = true;
+        self
+    }
+
+    fn write_field<T: Format<F> + ?Sized>(&mut self, name: &str, value: &T) -> Result<(), F::Error> {
+        self.fmt.push_piece(if self.has_fields { ", " } else { " { " })?;
+        self.fmt.push_piece(name)?;
+        self.fmt.push_piece(": ")?;
+        value.fmt(self.fmt)
+    }
+
+    /// Finish building, writing the closing brace if any fields were added.
+    pub fn finish(&mut self) -> Result<(), F::Error> {
+        if self.has_fields && self.result.is_ok() {
+            self.result = self.fmt.push_piece(" }");
+        }
+        ::core::mem::replace(&mut self.result, Ok(()))
+    }
+}
+
+/// Build a `Name(value, ..)`-style debug representation. See [`DebugTuple`].
+pub fn debug_tuple<'f, F: FormatTarget>(f: &'f mut F, name: &str) -> DebugTuple<'f, F> {
+    let result = f.push_piece(name);
+    DebugTuple {
+        fmt: f,
+        result,
+        fields: 0,
+    }
+}
+
+/// A builder for a tuple-struct-style debug representation, created with [`debug_tuple`].
+pub struct DebugTuple<'f, F: FormatTarget> {
+    fmt: &'f mut F,
+    result: Result<(), F::Error>,
+    fields: usize,
+}
+
+impl<F: FormatTarget> DebugTuple<'_, F> {
+    /// Add a field to the tuple output.
+    pub fn field<T: Format<F> + ?Sized>(&mut self, value: &T) -> &mut Self {
+        if self.result.is_ok() {
+            self.result = self.write_field(value);
+        }
+        self.fields += 1;
+        self
+    }
+
+    fn write_field<T: Format<F> + ?Sized>(&mut self, value: &T) -> Result<(), F::Error> {
+        self.fmt.push_piece(if self.fields == 0 { "(" } else { ", " })?;
+        value.fmt(self.fmt)
+    }
+
+    /// Finish building, writing the closing paren if any fields were added.
+    pub fn finish(&mut self) -> Result<(), F::Error> {
+        if self.fields > 0 && self.result.is_ok() {
+            self.result = self.fmt.push_piece(")");
+        }
+        ::core::mem::replace(&mut self.result, Ok(()))
+    }
+}
+
+/// Build a `[entry, ..]`-style debug representation. See [`DebugList`].
+pub fn debug_list<F: FormatTarget>(f: &mut F) -> DebugList<'_, F> {
+    let result = f.push_piece("[");
+    DebugList {
+        fmt: f,
+        result,
+        has_entries: false,
+    }
+}
+
+/// A builder for a list-style debug representation, created with [`debug_list`].
+pub struct DebugList<'f, F: FormatTarget> {
+    fmt: &'f mut F,
+    result: Result<(), F::Error>,
+    has_entries: bool,
+}
+
+impl<F: FormatTarget> DebugList<'_, F> {
+    /// Add a single entry to the list output.
+    pub fn entry<T: Format<F> + ?Sized>(&mut self, value: &T) -> &mut Self {
+        if self.result.is_ok() {
+            self.result = self.write_entry(value);
+        }
+        self.has_entries = true;
+        self
+    }
+
+    fn write_entry<T: Format<F> + ?Sized>(&mut self, value: &T) -> Result<(), F::Error> {
+        if self.has_entries {
+            self.fmt.push_piece(", ")?;
+        }
+        value.fmt(self.fmt)
+    }
+
+    /// Add entries from an iterator to the list output.
+    pub fn entries<T, I>(&mut self, entries: I) -> &mut Self
+    where
+        T: Format<F>,
+        I: IntoIterator<Item = T>,
+    {
+        for entry in entries {
+            self.entry(&entry);
+        }
+        self
+    }
+
+    /// Finish building, writing the closing bracket.
+    pub fn finish(&mut self) -> Result<(), F::Error> {
+        if self.result.is_ok() {
+            self.result = self.fmt.push_piece("]");
+        }
+        ::core::mem::replace(&mut self.result, Ok(()))
+    }
+}
+
+/// Build a `{entry, ..}`-style debug representation. See [`DebugSet`].
+pub fn debug_set<F: FormatTarget>(f: &mut F) -> DebugSet<'_, F> {
+    let result = f.push_piece("{");
+    DebugSet {
+        fmt: f,
+        result,
+        has_entries: false,
+    }
+}
+
+/// A builder for a set-style debug representation, created with [`debug_set`].
+pub struct DebugSet<'f, F: FormatTarget> {
+    fmt: &'f mut F,
+    result: Result<(), F::Error>,
+    has_entries: bool,
+}
+
+impl<F: FormatTarget> DebugSet<'_, F> {
+    /// Add a single entry to the set output.
+    pub fn entry<T: Format<F> + ?Sized>(&mut self, value: &T) -> &mut Self {
+        if self.result.is_ok() {
+            self.result = self.write_entry(value);
+        }
+        self.has_entries = true;
+        self
+    }
+
+    fn write_entry<T: Format<F> + ?Sized>(&mut self, value: &T) -> Result<(), F::Error> {
+        if self.has_entries {
+            self.fmt.push_piece(", ")?;
+        }
+        value.fmt(self.fmt)
+    }
+
+    /// Add entries from an iterator to the set output.
+    pub fn entries<T, I>(&mut self, entries: I) -> &mut Self
+    where
+        T: Format<F>,
+        I: IntoIterator<Item = T>,
+    {
+        for entry in entries {
+            self.entry(&entry);
+        }
+        self
+    }
+
+    /// Finish building, writing the closing brace.
+    pub fn finish(&mut self) -> Result<(), F::Error> {
+        if self.result.is_ok() {
+            self.result = self.fmt.push_piece("}");
+        }
+        ::core::mem::replace(&mut self.result, Ok(()))
+    }
+}
+
+/// Build a `{key: value, ..}`-style debug representation. See [`DebugMap`].
+pub fn debug_map<F: FormatTarget>(f: &mut F) -> DebugMap<'_, F> {
+    let result = f.push_piece("{");
+    DebugMap {
+        fmt: f,
+        result,
+        has_entries: false,
+    }
+}
+
+/// A builder for a map-style debug representation, created with [`debug_map`].
+pub struct DebugMap<'f, F: FormatTarget> {
+    fmt: &'f mut F,
+    result: Result<(), F::Error>,
+    has_entries: bool,
+}
+
+impl<F: FormatTarget> DebugMap<'_, F> {
+    /// Add a key-value entry to the map output.
+    pub fn entry<K: Format<F> + ?Sized, V: Format<F> + ?Sized>(
+        &mut self,
+        key: &K,
+        value: &V,
+    ) -> &mut Self {
+        if self.result.is_ok() {
+            self.result = self.write_entry(key, value);
+        }
+        self.has_entries = true;
+        self
+    }
+
+    fn write_entry<K: Format<F> + ?Sized, V: Format<F> + ?Sized>(
+        &mut self,
+        key: &K,
+        value: &V,
+    ) -> Result<(), F::Error> {
+        if self.has_entries {
+            self.fmt.push_piece(", ")?;
+        }
+        key.fmt(self.fmt)?;
+        self.fmt.push_piece(": ")?;
+        value.fmt(self.fmt)
+    }
+
+    /// Add key-value entries from an iterator to the map output.
+    pub fn entries<K, V, I>(&mut self, entries: I) -> &mut Self
+    where
+        K: Format<F>,
+        V: Format<F>,
+        I: IntoIterator<Item = (K, V)>,
+    {
+        for (key, value) in entries {
+            self.entry(&key, &value);
+        }
+        self
+    }
+
+    /// Finish building, writing the closing brace.
+    pub fn finish(&mut self) -> Result<(), F::Error> {
+        if self.result.is_ok() {
+            self.result = self.fmt.push_piece("}");
+        }
+        ::core::mem::replace(&mut self.result, Ok(()))
+    }
+}
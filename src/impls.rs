@@ -1,19 +1,17 @@
 use super::*;
 use std::{fmt::Write as FmtWrite, io::Write as IoWrite};
 
+impl FormatTarget for Vec<u8> {
+    type Error = std::io::Error;
+    fn push_piece(&mut self, piece: &str) -> Result<(), std::io::Error> {
+        self.write_all(piece.as_bytes())
+    }
+}
 impl CustomFormatter for Vec<u8> {
     type Output = Self;
-    type Error = std::io::Error;
     fn from_args(args: Arguments<'_, Self>) -> Result<Self, std::io::Error> {
         let mut self_ = Vec::with_capacity(args.estimated_total_capacity());
-
-        for (piece, arg) in args {
-            self_.write(piece.as_bytes())?;
-            if let Some(arg) = arg {
-                arg.fmt(&mut self_)?;
-            }
-        }
-
+        args.write_into(&mut self_)?;
         Ok(self_)
     }
 }
@@ -21,10 +19,59 @@ impl Format<Vec<u8>> for &str {
     fn fmt(&self, f: &mut Vec<u8>) -> Result<(), std::io::Error> {
         f.write(self.as_bytes()).map(|_| ())
     }
+    fn fmt_spec(&self, f: &mut Vec<u8>, spec: &FormatSpec) -> Result<(), std::io::Error> {
+        write_padded(f, truncate(self, spec.precision), spec, Align::Left)
+    }
     fn estimated_capacity(&self) -> usize {
         self.len()
     }
 }
+
+/// Truncate `s` to `precision` chars, or return it unchanged if `precision` is `None`.
+fn truncate(s: &str, precision: Option<usize>) -> &str {
+    match precision {
+        Some(precision) => {
+            let end = s
+                .char_indices()
+                .nth(precision)
+                .map(|(i, _)| i)
+                .unwrap_or(s.len());
+            &s[..end]
+        }
+        None => s,
+    }
+}
+
+/// Pad `content` to `spec.width` using `spec.fill`, aligned per `spec.align` (falling back to
+/// `default_align` when the spec doesn't request one).
+fn write_padded<W: IoWrite + ?Sized>(
+    f: &mut W,
+    content: &str,
+    spec: &FormatSpec,
+    default_align: Align,
+) -> Result<(), std::io::Error> {
+    let pad = spec
+        .width
+        .unwrap_or(0)
+        .saturating_sub(content.chars().count());
+    let (left, right) = match spec.align.unwrap_or(default_align) {
+        Align::Left => (0, pad),
+        Align::Right => (pad, 0),
+        Align::Center => (pad / 2, pad - pad / 2),
+    };
+    let mut fill_buf = [0u8; 4];
+    let fill = spec.fill.encode_utf8(&mut fill_buf).as_bytes();
+
+    for _ in 0..left {
+        f.write_all(fill)?;
+    }
+    f.write_all(content.as_bytes())?;
+    for _ in 0..right {
+        f.write_all(fill)?;
+    }
+
+    Ok(())
+}
 impl Format<Vec<u8>> for u8 {
     fn fmt(&self, f: &mut Vec<u8>) -> Result<(), std::io::Error> {
         Ok(f.push(*self))
@@ -34,7 +81,7 @@ impl Format<Vec<u8>> for u8 {
     }
 }
 impl Format<Vec<u8>> for &[u8] {
-    fn fmt(&self, f: &mut Vec<u8>) -> Result<(), <Vec<u8> as CustomFormatter>::Error> {
+    fn fmt(&self, f: &mut Vec<u8>) -> Result<(), <Vec<u8> as FormatTarget>::Error> {
         f.extend_from_slice(self);
 
         Ok(())
@@ -44,24 +91,79 @@ impl Format<Vec<u8>> for &[u8] {
     }
 }
 impl<T: Format<Vec<u8>>> Format<Vec<u8>> for &T {
-    fn fmt(&self, f: &mut Vec<u8>) -> Result<(), <Vec<u8> as CustomFormatter>::Error> {
+    fn fmt(&self, f: &mut Vec<u8>) -> Result<(), <Vec<u8> as FormatTarget>::Error> {
+        T::fmt(self, f)
+    }
+    fn fmt_spec(
+        &self,
+        f: &mut Vec<u8>,
+        spec: &FormatSpec,
+    ) -> Result<(), <Vec<u8> as FormatTarget>::Error> {
+        T::fmt_spec(self, f, spec)
+    }
+}
+
+impl<W: IoWrite> FormatTarget for WriterFormatter<W> {
+    type Error = std::io::Error;
+    fn push_piece(&mut self, piece: &str) -> Result<(), std::io::Error> {
+        self.0.write_all(piece.as_bytes())
+    }
+}
+impl<W: IoWrite + Default> CustomFormatter for WriterFormatter<W> {
+    type Output = W;
+    fn from_args(args: Arguments<'_, Self>) -> Result<W, std::io::Error> {
+        let mut self_ = Self(W::default());
+        args.write_into(&mut self_)?;
+        Ok(self_.0)
+    }
+}
+impl<W: IoWrite> Format<WriterFormatter<W>> for &str {
+    fn fmt(&self, f: &mut WriterFormatter<W>) -> Result<(), std::io::Error> {
+        f.0.write_all(self.as_bytes())
+    }
+    fn fmt_spec(&self, f: &mut WriterFormatter<W>, spec: &FormatSpec) -> Result<(), std::io::Error> {
+        write_padded(&mut f.0, truncate(self, spec.precision), spec, Align::Left)
+    }
+    fn estimated_capacity(&self) -> usize {
+        self.len()
+    }
+}
+impl<W: IoWrite> Format<WriterFormatter<W>> for u8 {
+    fn fmt(&self, f: &mut WriterFormatter<W>) -> Result<(), std::io::Error> {
+        f.0.write_all(&[*self])
+    }
+    fn estimated_capacity(&self) -> usize {
+        1
+    }
+}
+impl<W: IoWrite> Format<WriterFormatter<W>> for &[u8] {
+    fn fmt(&self, f: &mut WriterFormatter<W>) -> Result<(), std::io::Error> {
+        f.0.write_all(self)
+    }
+    fn estimated_capacity(&self) -> usize {
+        self.len()
+    }
+}
+impl<W: IoWrite, T: Format<WriterFormatter<W>>> Format<WriterFormatter<W>> for &T {
+    fn fmt(&self, f: &mut WriterFormatter<W>) -> Result<(), std::io::Error> {
         T::fmt(self, f)
     }
+    fn fmt_spec(&self, f: &mut WriterFormatter<W>, spec: &FormatSpec) -> Result<(), std::io::Error> {
+        T::fmt_spec(self, f, spec)
+    }
 }
 
+impl FormatTarget for DebugFormatter {
+    type Error = std::fmt::Error;
+    fn push_piece(&mut self, piece: &str) -> std::fmt::Result {
+        self.0.write_str(piece)
+    }
+}
 impl CustomFormatter for DebugFormatter {
     type Output = String;
-    type Error = std::fmt::Error;
     fn from_args(args: Arguments<'_, Self>) -> Result<Self::Output, Self::Error> {
         let mut self_ = Self(String::with_capacity(args.estimated_total_capacity()));
-
-        for (piece, arg) in args {
-            self_.0.write_str(piece)?;
-            if let Some(arg) = arg {
-                arg.fmt(&mut self_)?;
-            }
-        }
-
+        args.write_into(&mut self_)?;
         Ok(self_.0)
     }
 }
@@ -73,24 +175,59 @@ where
     fn fmt(
         &self,
         f: &mut DebugFormatter,
-    ) -> Result<(), <DebugFormatter as CustomFormatter>::Error> {
+    ) -> Result<(), <DebugFormatter as FormatTarget>::Error> {
         f.0.write_fmt(format_args!("{self:?}"))
     }
+    fn fmt_spec(
+        &self,
+        f: &mut DebugFormatter,
+        spec: &FormatSpec,
+    ) -> Result<(), <DebugFormatter as FormatTarget>::Error> {
+        write_padded_str(&mut f.0, &format!("{self:?}"), spec, Align::Left)
+    }
 }
 
+/// Pad/truncate a rendered value into a string sink, mirroring [`write_padded`] for the
+/// `Vec<u8>` formatters.
+fn write_padded_str<W: FmtWrite + ?Sized>(
+    out: &mut W,
+    rendered: &str,
+    spec: &FormatSpec,
+    default_align: Align,
+) -> std::fmt::Result {
+    let truncated = truncate(rendered, spec.precision);
+    let pad = spec
+        .width
+        .unwrap_or(0)
+        .saturating_sub(truncated.chars().count());
+    let (left, right) = match spec.align.unwrap_or(default_align) {
+        Align::Left => (0, pad),
+        Align::Right => (pad, 0),
+        Align::Center => (pad / 2, pad - pad / 2),
+    };
+
+    for _ in 0..left {
+        out.write_char(spec.fill)?;
+    }
+    out.write_str(truncated)?;
+    for _ in 0..right {
+        out.write_char(spec.fill)?;
+    }
+
+    Ok(())
+}
+
+impl FormatTarget for DisplayFormatter {
+    type Error = std::fmt::Error;
+    fn push_piece(&mut self, piece: &str) -> std::fmt::Result {
+        self.0.write_str(piece)
+    }
+}
 impl CustomFormatter for DisplayFormatter {
     type Output = String;
-    type Error = std::fmt::Error;
     fn from_args(args: Arguments<'_, Self>) -> Result<Self::Output, Self::Error> {
         let mut self_ = Self(String::with_capacity(args.estimated_total_capacity()));
-
-        for (piece, arg) in args {
-            self_.0.write_str(piece)?;
-            if let Some(arg) = arg {
-                arg.fmt(&mut self_)?;
-            }
-        }
-
+        args.write_into(&mut self_)?;
         Ok(self_.0)
     }
 }
@@ -102,7 +239,48 @@ where
     fn fmt(
         &self,
         f: &mut DisplayFormatter,
-    ) -> Result<(), <DebugFormatter as CustomFormatter>::Error> {
+    ) -> Result<(), <DisplayFormatter as FormatTarget>::Error> {
         f.0.write_fmt(format_args!("{self}"))
     }
+    fn fmt_spec(
+        &self,
+        f: &mut DisplayFormatter,
+        spec: &FormatSpec,
+    ) -> Result<(), <DisplayFormatter as FormatTarget>::Error> {
+        write_padded_str(&mut f.0, &format!("{self}"), spec, Align::Left)
+    }
+}
+
+impl<W: FmtWrite> FormatTarget for FmtWriterFormatter<W> {
+    type Error = std::fmt::Error;
+    fn push_piece(&mut self, piece: &str) -> std::fmt::Result {
+        self.0.write_str(piece)
+    }
+}
+impl<W: FmtWrite + Default> CustomFormatter for FmtWriterFormatter<W> {
+    type Output = W;
+    fn from_args(args: Arguments<'_, Self>) -> Result<W, std::fmt::Error> {
+        let mut self_ = Self(W::default());
+        args.write_into(&mut self_)?;
+        Ok(self_.0)
+    }
+}
+impl<W: FmtWrite> Format<FmtWriterFormatter<W>> for &str {
+    fn fmt(&self, f: &mut FmtWriterFormatter<W>) -> std::fmt::Result {
+        f.0.write_str(self)
+    }
+    fn fmt_spec(&self, f: &mut FmtWriterFormatter<W>, spec: &FormatSpec) -> std::fmt::Result {
+        write_padded_str(&mut f.0, truncate(self, spec.precision), spec, Align::Left)
+    }
+    fn estimated_capacity(&self) -> usize {
+        self.len()
+    }
+}
+impl<W: FmtWrite, T: Format<FmtWriterFormatter<W>>> Format<FmtWriterFormatter<W>> for &T {
+    fn fmt(&self, f: &mut FmtWriterFormatter<W>) -> std::fmt::Result {
+        T::fmt(self, f)
+    }
+    fn fmt_spec(&self, f: &mut FmtWriterFormatter<W>, spec: &FormatSpec) -> std::fmt::Result {
+        T::fmt_spec(self, f, spec)
+    }
 }